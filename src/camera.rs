@@ -0,0 +1,94 @@
+
+use dacite::core::Extent2D;
+use siege_math::{Angle, Mat4, Vec2, Vec4};
+
+pub const NEAR_PLANE: f32 = 0.1;
+pub const FAR_PLANE: f32 = 10000.0;
+
+/// How `Camera` projects eye-space coordinates into clip space.
+#[derive(Debug, Clone, Copy)]
+pub enum ProjectionMode {
+    /// A standard FPS-style frustum, defined by its horizontal field of view.
+    Perspective { fovx: Angle<f32> },
+    /// A parallel projection, defined by its vertical extent in world units.
+    /// Useful for UI overlays, top-down/2D rendering, and shadow-style setups.
+    Orthographic { height: f32 },
+}
+
+/// Per-eye configuration for stereo (VR) rendering. When set on `Camera`,
+/// `CameraGfx` derives a left and right eye view/projection pair from the
+/// camera's single `view_matrix`, offset along its local right axis.
+#[derive(Debug, Clone, Copy)]
+pub struct StereoConfig {
+    /// Distance in world units between the two eyes.
+    pub interpupillary_distance: f32,
+    /// Distance to the plane both eyes' frustums converge on. Used to skew
+    /// the per-eye frustums instead of toeing the view matrices in, which
+    /// would otherwise introduce unwanted vertical parallax. Values below
+    /// `NEAR_PLANE` (including zero and negative ones) are clamped up to
+    /// `NEAR_PLANE` when the eye projections are built.
+    pub convergence_distance: f32,
+}
+
+/// A sub-rectangle of the render target a camera renders into. `extent` is
+/// the viewport's own size, not necessarily the whole framebuffer, so that
+/// several cameras can coexist and render into disjoint regions of the same
+/// target (split-screen, picture-in-picture, etc).
+#[derive(Debug, Clone, Copy)]
+pub struct Viewport {
+    pub origin: Vec2<f32>,
+    pub extent: Extent2D,
+}
+
+impl Viewport {
+    /// A viewport covering the whole of `extent`, i.e. the ordinary
+    /// single-camera case.
+    pub fn full(extent: Extent2D) -> Viewport {
+        Viewport { origin: Vec2::zero(), extent: extent }
+    }
+}
+
+pub struct Camera {
+    pub projection_mode: ProjectionMode,
+    pub view_matrix: Mat4<f32>,
+    /// World-space position of the camera, for eye-space lighting math.
+    pub position_wspace: Vec4<f32>,
+    /// The camera's viewport extent (not necessarily the whole framebuffer).
+    pub extent: Extent2D,
+    /// Where the viewport sits within the framebuffer. Screen-space shaders
+    /// should derive UVs as `(frag_coord.xy - viewport_origin) / extent`
+    /// rather than assuming the camera owns the whole framebuffer.
+    pub viewport_origin: Vec2<f32>,
+    /// `Some` to enable stereo (VR) uniforms alongside the normal cyclopean
+    /// ones; `None` for ordinary single-eye rendering.
+    pub stereo: Option<StereoConfig>,
+    /// Use a reverse-Z projection (near maps to 1, far maps to 0) instead of
+    /// the standard near-to-0/far-to-1 mapping. Dramatically improves
+    /// floating-point depth precision at the large near/far ranges typical
+    /// of FPS scenes. The renderer must pair this with a `GREATER` depth
+    /// test and a depth buffer cleared to 0 instead of 1. Applies to
+    /// `ProjectionMode::Perspective` only; the orthographic projection
+    /// always maps near to 0 and far to 1.
+    pub reverse_z: bool,
+}
+
+impl Camera {
+    pub fn new(extent: Extent2D) -> Camera {
+        Camera {
+            projection_mode: ProjectionMode::Perspective {
+                fovx: Angle::Radians(1.5708), // ~90 degrees
+            },
+            view_matrix: Mat4::identity(),
+            position_wspace: Vec4::new(0.0, 0.0, 0.0, 1.0),
+            extent: extent,
+            viewport_origin: Vec2::zero(),
+            stereo: None,
+            reverse_z: false,
+        }
+    }
+
+    pub fn set_viewport(&mut self, viewport: Viewport) {
+        self.viewport_origin = viewport.origin;
+        self.extent = viewport.extent;
+    }
+}
@@ -0,0 +1,156 @@
+
+use std::f32::consts::PI;
+use std::sync::{Arc, RwLock};
+use siege_math::{Angle, Mat4, Quat, Vec3, Vec4};
+use super::Camera;
+
+/// Limit on `FlyCamController::pitch`, just shy of ±90 degrees so the view
+/// matrix never flips upside-down (gimbal flip) when looking straight up
+/// or down.
+pub const MAX_PITCH: f32 = (PI / 2.0) - 0.01;
+
+/// Tunables for a `FlyCamController`.
+pub struct FlyCamConfig {
+    /// World units moved per second at full input magnitude.
+    pub move_speed: f32,
+    /// Radians of yaw/pitch per unit of mouse-delta input.
+    pub mouse_sensitivity: f32,
+    /// How quickly velocity catches up to the input-driven target velocity,
+    /// in 1/seconds. `0.0` disables smoothing (instant acceleration).
+    pub acceleration: f32,
+}
+
+impl Default for FlyCamConfig {
+    fn default() -> FlyCamConfig {
+        FlyCamConfig {
+            move_speed: 4.0,
+            mouse_sensitivity: 0.0025,
+            acceleration: 12.0,
+        }
+    }
+}
+
+/// Per-frame input for a `FlyCamController`. The host app fills this in from
+/// whatever windowing/input layer it uses and passes it to `tick()`; this
+/// crate doesn't hardcode a keyboard or mouse binding.
+#[derive(Default, Clone, Copy)]
+pub struct FlyCamInput {
+    /// -1.0 (backward) .. 1.0 (forward)
+    pub move_forward: f32,
+    /// -1.0 (left) .. 1.0 (right)
+    pub move_right: f32,
+    /// -1.0 (down) .. 1.0 (up)
+    pub move_up: f32,
+    /// Mouse (or look-stick) movement since the last tick, in whatever units
+    /// `FlyCamConfig::mouse_sensitivity` is calibrated against.
+    pub look_delta_x: f32,
+    pub look_delta_y: f32,
+}
+
+/// An input-driven first-person flycam. Owns yaw/pitch/position state,
+/// consumes per-frame `FlyCamInput`, and on each `tick()` writes the
+/// resulting view matrix and world-space position into the shared `Camera`
+/// that `CameraGfx::update` already reads.
+pub struct FlyCamController {
+    pub config: FlyCamConfig,
+    pub position: Vec3<f32>,
+    pub yaw: f32,
+    pub pitch: f32,
+    velocity: Vec3<f32>,
+}
+
+impl FlyCamController {
+    pub fn new(position: Vec3<f32>) -> FlyCamController {
+        FlyCamController {
+            config: FlyCamConfig::default(),
+            position: position,
+            yaw: 0.0,
+            pitch: 0.0,
+            velocity: Vec3::zero(),
+        }
+    }
+
+    /// Advances the controller by `dt` seconds given this frame's `input`,
+    /// and writes the resulting view matrix and world position into `camera`.
+    pub fn tick(&mut self, input: &FlyCamInput, dt: f32, camera: &Arc<RwLock<Camera>>) {
+        self.yaw -= input.look_delta_x * self.config.mouse_sensitivity;
+        self.pitch -= input.look_delta_y * self.config.mouse_sensitivity;
+        self.pitch = self.pitch.max(-MAX_PITCH).min(MAX_PITCH);
+
+        // Movement is yaw-only (no flying sideways just from looking up or
+        // down), the usual FPS convention.
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let forward = Vec3::new(sin_yaw, 0.0, cos_yaw);
+        let right = Vec3::new(cos_yaw, 0.0, -sin_yaw);
+        let up = Vec3::new(0.0, 1.0, 0.0);
+
+        let target_velocity =
+            (forward * input.move_forward + right * input.move_right + up * input.move_up)
+            * self.config.move_speed;
+
+        if self.config.acceleration <= 0.0 {
+            self.velocity = target_velocity;
+        } else {
+            let t = (self.config.acceleration * dt).min(1.0);
+            self.velocity = self.velocity + (target_velocity - self.velocity) * t;
+        }
+        self.position = self.position + self.velocity * dt;
+
+        let orientation =
+            Quat::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), Angle::Radians(self.yaw))
+            * Quat::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), Angle::Radians(self.pitch));
+        let rotation: Mat4<f32> = Mat4::from(orientation);
+
+        let translation = Mat4::new(
+            1.0, 0.0, 0.0, self.position.x,
+            0.0, 1.0, 0.0, self.position.y,
+            0.0, 0.0, 1.0, self.position.z,
+            0.0, 0.0, 0.0, 1.0,
+        );
+        let camera_to_world = &translation * &rotation;
+
+        let mut c = camera.write().unwrap();
+        c.view_matrix = camera_to_world.inverse().unwrap();
+        c.position_wspace = Vec4::new(self.position.x, self.position.y, self.position.z, 1.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dacite::core::Extent2D;
+    use super::{FlyCamController, FlyCamInput, MAX_PITCH, Vec3};
+    use super::super::Camera;
+    use std::sync::{Arc, RwLock};
+
+    #[test]
+    fn move_forward_advances_along_the_yaw_zero_forward_axis() {
+        let camera = Arc::new(RwLock::new(Camera::new(Extent2D { width: 1280, height: 720 })));
+        let mut flycam = FlyCamController::new(Vec3::zero());
+
+        let input = FlyCamInput { move_forward: 1.0, ..FlyCamInput::default() };
+        // acceleration * dt >= 1.0, so velocity fully converges to the
+        // target this tick and the displacement is exactly move_speed.
+        flycam.tick(&input, 1.0, &camera);
+
+        // At yaw == 0.0 "forward" is +Z, this engine's eye-space convention.
+        assert!((flycam.position.x).abs() < 1e-4);
+        assert!((flycam.position.y).abs() < 1e-4);
+        assert!((flycam.position.z - flycam.config.move_speed).abs() < 1e-4);
+    }
+
+    #[test]
+    fn pitch_saturates_at_max_pitch() {
+        let camera = Arc::new(RwLock::new(Camera::new(Extent2D { width: 1280, height: 720 })));
+        let mut flycam = FlyCamController::new(Vec3::zero());
+
+        // A huge downward look-delta would overshoot ±90 degrees without
+        // clamping.
+        let input = FlyCamInput { look_delta_y: -1.0e6, ..FlyCamInput::default() };
+        flycam.tick(&input, 1.0, &camera);
+        assert_eq!(flycam.pitch, MAX_PITCH);
+
+        let input = FlyCamInput { look_delta_y: 1.0e6, ..FlyCamInput::default() };
+        flycam.tick(&input, 1.0, &camera);
+        assert_eq!(flycam.pitch, -MAX_PITCH);
+    }
+}
@@ -3,18 +3,29 @@ use std::sync::{Arc, RwLock};
 use dacite::core::{DescriptorSetLayout, DescriptorSetLayoutBinding,
                    DescriptorSet, WriteDescriptorSetElements,
                    CommandBuffer, Extent2D};
-use siege_math::{Mat4, Vec4};
+use siege_math::{Mat4, Vec2, Vec4};
 use siege_render::{Renderer, HostVisibleBuffer, Lifetime, Plugin,
                    Params, Stats};
 use super::Camera;
+use super::camera::{ProjectionMode, Viewport};
 //use errors::*;
 
 pub struct RenderParams {
     pub bloom_strength: f32,
     pub bloom_cliff: f32,
     pub blur_level: f32,
+    /// When true, only the left eye's geometry is rendered; the right eye
+    /// is synthesized by reprojecting the left eye's color+depth instead of
+    /// recording the scene twice. Roughly halves geometry cost for stereo
+    /// output at the expense of minor disocclusion artifacts.
+    pub stereo_reprojection: bool,
 }
 
+/// `CameraUniforms::projection_mode` when the camera is perspective.
+pub const PROJECTION_MODE_PERSPECTIVE: u32 = 0;
+/// `CameraUniforms::projection_mode` when the camera is orthographic.
+pub const PROJECTION_MODE_ORTHOGRAPHIC: u32 = 1;
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 pub struct CameraUniforms {
@@ -26,12 +37,95 @@ pub struct CameraUniforms {
     pub white_level: f32,
     pub extent: Extent2D,
     pub fovx: f32,
+    // `fovx` above is only meaningful when
+    // `projection_mode == PROJECTION_MODE_PERSPECTIVE`; it is 0.0 otherwise.
+    pub projection_mode: u32,
+    /// Where the viewport sits within the framebuffer. Shaders deriving
+    /// screen-space UVs from `gl_FragCoord` should use
+    /// `(frag_coord.xy - viewport_origin) / extent` rather than assuming
+    /// the camera owns the whole framebuffer.
+    pub viewport_origin: Vec2<f32>,
+}
+
+/// CPU-only bookkeeping for `CameraUniforms::update`'s lazy projection
+/// rebuild: the `(mode, param, extent, reverse_z)` `update()` last built
+/// `projection_matrix` from, so it isn't rebuilt every frame when nothing
+/// affecting it has changed. Kept out of `CameraUniforms` itself since that
+/// struct is uploaded to the GPU verbatim via `write_one`.
+#[derive(Debug, Clone, Copy)]
+struct ProjectionCache {
+    mode: u32,
+    param: f32,
+    extent: Extent2D,
+    reverse_z: bool,
+}
+
+impl ProjectionCache {
+    /// Sentinel guaranteed to differ from any real camera, so the first
+    /// `update()` call always builds the projection matrix.
+    fn stale() -> ProjectionCache {
+        ProjectionCache {
+            mode: ::std::u32::MAX,
+            param: ::std::f32::NAN,
+            extent: Extent2D { width: 0, height: 0 },
+            reverse_z: false,
+        }
+    }
+}
+
+/// The combined view-projection matrix alone, for passes that only need to
+/// transform geometry and don't care about eye-space lighting or screen
+/// extent. Bound separately so such passes don't have to pull in the rest
+/// of `CameraUniforms`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct CameraViewProj {
+    pub projection_x_view_matrix: Mat4<f32>,
+}
+
+/// The view matrix plus the camera's world-space position and viewport
+/// extent, for passes that work in eye space (lighting, fog, etc.) but
+/// don't need the projection matrix.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct CameraView {
+    pub view_matrix: Mat4<f32>,
+    pub camera_position_wspace: Vec4<f32>,
+    pub extent: Extent2D,
+    pub viewport_origin: Vec2<f32>,
+}
+
+/// All the matrices a single eye needs to render and, if it's the
+/// reprojection target, to be synthesized from the other eye.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct EyeUniforms {
+    pub view_matrix: Mat4<f32>,
+    pub projection_matrix: Mat4<f32>,
+    pub projection_x_view_matrix: Mat4<f32>,
+    /// Full inverse of `projection_x_view_matrix`, so a reprojection pass can
+    /// unproject this eye's depth buffer back to world/eye space.
+    pub inv_view_proj_matrix: Mat4<f32>,
+}
+
+/// Left/right eye uniforms for stereo (VR) rendering. Bound separately from
+/// `CameraUniforms` since mono passes never need it.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct StereoUniforms {
+    pub left: EyeUniforms,
+    pub right: EyeUniforms,
+    /// Non-zero when the right eye was synthesized by reprojecting the left
+    /// eye's color+depth rather than rendered directly.
+    pub reprojected: u32,
 }
 
 impl CameraUniforms {
-    pub fn new(camera: &Camera)
-               -> CameraUniforms
-    {
+    /// Builds the uniforms plus the (otherwise internal) cache `update()`
+    /// needs to skip rebuilding the projection matrix on unchanged frames;
+    /// callers hold on to the `ProjectionCache` and pass it back into every
+    /// subsequent `update()` call for this camera.
+    fn new(camera: &Camera) -> (CameraUniforms, ProjectionCache) {
         let mut uniforms = CameraUniforms {
             projection_x_view_matrix: Mat4::identity(),
             view_matrix: Mat4::identity(),
@@ -41,30 +135,152 @@ impl CameraUniforms {
             white_level: 0.08,
             extent: Extent2D { width: 1280, height: 1024 }, // will be updated
             fovx: 0.0, // will be updated
+            projection_mode: PROJECTION_MODE_PERSPECTIVE, // will be updated
+            viewport_origin: Vec2::zero(), // will be updated
         };
-        uniforms.update(camera, Vec4::zero());
-        uniforms
+        let mut cache = ProjectionCache::stale();
+        uniforms.update(camera, &mut cache);
+        (uniforms, cache)
     }
 
-    pub fn update(&mut self, camera: &Camera, position_wspace: Vec4<f32>)
+    pub fn update(&mut self, camera: &Camera, cache: &mut ProjectionCache)
     {
-        let (fovx, view_matrix, extent) = {
-            (camera.fovx.as_radians(), camera.view_matrix, camera.extent)
+        let (projection_mode, view_matrix, extent, viewport_origin, position_wspace, reverse_z) = {
+            (camera.projection_mode, camera.view_matrix, camera.extent, camera.viewport_origin,
+             camera.position_wspace, camera.reverse_z)
         };
         self.camera_position_wspace = position_wspace;
-        self.fovx = fovx;
         self.view_matrix = view_matrix;
         self.extent = extent;
+        self.viewport_origin = viewport_origin;
 
-        // Fixme - only redo projection matrix if extent changes OR fovx changes.
-        // Right now I have no idea of FOV changed, so we always redo it:
-        let ar: f32 = self.extent.width as f32 / self.extent.height as f32;
-        self.projection_matrix = perspective_matrix_fov_vulkan(
-            self.fovx, ar, ::camera::NEAR_PLANE, ::camera::FAR_PLANE);
+        let (mode, param) = match projection_mode {
+            ProjectionMode::Perspective { fovx } =>
+                (PROJECTION_MODE_PERSPECTIVE, fovx.as_radians()),
+            ProjectionMode::Orthographic { height } =>
+                (PROJECTION_MODE_ORTHOGRAPHIC, height),
+        };
+
+        // Only rebuild the projection matrix if the mode, its one scalar
+        // parameter (fovx or height), the extent, or the reverse-Z flag
+        // actually changed; the view changes every frame regardless, so
+        // `projection_x_view_matrix` below is always redone.
+        if mode != cache.mode
+            || param != cache.param
+            || extent.width != cache.extent.width
+            || extent.height != cache.extent.height
+            || reverse_z != cache.reverse_z
+        {
+            let ar: f32 = extent.width as f32 / extent.height as f32;
+
+            self.projection_mode = mode;
+            self.projection_matrix = match projection_mode {
+                ProjectionMode::Perspective { .. } => {
+                    self.fovx = param;
+                    if reverse_z {
+                        perspective_matrix_fov_vulkan_reverse_z(
+                            param, ar, ::camera::NEAR_PLANE, ::camera::FAR_PLANE)
+                    } else {
+                        perspective_matrix_fov_vulkan(
+                            param, ar, ::camera::NEAR_PLANE, ::camera::FAR_PLANE)
+                    }
+                }
+                ProjectionMode::Orthographic { .. } => {
+                    self.fovx = 0.0; // not meaningful in orthographic mode
+                    orthographic_matrix_vulkan(
+                        param, ar, ::camera::NEAR_PLANE, ::camera::FAR_PLANE)
+                }
+            };
+
+            cache.mode = mode;
+            cache.param = param;
+            cache.extent = extent;
+            cache.reverse_z = reverse_z;
+        }
 
         self.projection_x_view_matrix =
             &self.projection_matrix * &self.view_matrix;
     }
+
+    pub fn view_proj(&self) -> CameraViewProj {
+        CameraViewProj {
+            projection_x_view_matrix: self.projection_x_view_matrix,
+        }
+    }
+
+    pub fn view(&self) -> CameraView {
+        CameraView {
+            view_matrix: self.view_matrix,
+            camera_position_wspace: self.camera_position_wspace,
+            extent: self.extent,
+            viewport_origin: self.viewport_origin,
+        }
+    }
+
+    /// Derives left/right eye uniforms from `camera.stereo`, or `None` if
+    /// stereo isn't configured (or the camera is orthographic, which this
+    /// doesn't support). Respects `camera.reverse_z`, so both eyes stay in
+    /// the same depth convention as the mono projection.
+    pub fn stereo(&self, camera: &Camera) -> Option<StereoUniforms> {
+        let stereo = match camera.stereo {
+            Some(stereo) => stereo,
+            None => return None,
+        };
+        let fovx = match camera.projection_mode {
+            ProjectionMode::Perspective { fovx } => fovx.as_radians(),
+            ProjectionMode::Orthographic { .. } => return None,
+        };
+
+        let ar: f32 = self.extent.width as f32 / self.extent.height as f32;
+        let half_ipd = stereo.interpupillary_distance / 2.0;
+        let reverse_z = camera.reverse_z;
+        // Guard against a zero/negative convergence distance (e.g. a
+        // default-initialized `StereoConfig`): it divides into the skew term
+        // below, and a non-finite skew would propagate into the projection
+        // matrix and panic the `inverse().unwrap()` further down.
+        let convergence_distance = stereo.convergence_distance.max(::camera::NEAR_PLANE);
+
+        let make_eye = |offset_x: f32| -> EyeUniforms {
+            let view_matrix = eye_offset_view_matrix(self.view_matrix, offset_x);
+            let projection_matrix = if reverse_z {
+                perspective_matrix_fov_vulkan_asymmetric_reverse_z(
+                    fovx, ar, ::camera::NEAR_PLANE, ::camera::FAR_PLANE,
+                    offset_x, convergence_distance)
+            } else {
+                perspective_matrix_fov_vulkan_asymmetric(
+                    fovx, ar, ::camera::NEAR_PLANE, ::camera::FAR_PLANE,
+                    offset_x, convergence_distance)
+            };
+            let projection_x_view_matrix = &projection_matrix * &view_matrix;
+            EyeUniforms {
+                view_matrix: view_matrix,
+                projection_matrix: projection_matrix,
+                projection_x_view_matrix: projection_x_view_matrix,
+                inv_view_proj_matrix: projection_x_view_matrix.inverse().unwrap(),
+            }
+        };
+
+        Some(StereoUniforms {
+            left: make_eye(-half_ipd),
+            right: make_eye(half_ipd),
+            reprojected: 0,
+        })
+    }
+}
+
+/// Offsets a view matrix along the camera's local right axis by `offset_x`
+/// world units. Since `view_matrix` already transforms world space into the
+/// camera's local space, shifting the eye right by `offset_x` in world space
+/// is equivalent to translating the already-transformed coordinates left by
+/// `offset_x` in view space.
+fn eye_offset_view_matrix(view_matrix: Mat4<f32>, offset_x: f32) -> Mat4<f32> {
+    let translate = Mat4::new(
+        1.0, 0.0, 0.0, -offset_x,
+        0.0, 1.0, 0.0, 0.0,
+        0.0, 0.0, 1.0, 0.0,
+        0.0, 0.0, 0.0, 1.0,
+    );
+    &translate * &view_matrix
 }
 
 /// Generates a perspective matrix, mapping "eye" coordinates (a truncated pyramid
@@ -88,6 +304,133 @@ fn perspective_matrix_fov_vulkan(
     )
 }
 
+/// A reverse-Z variant of `perspective_matrix_fov_vulkan`: maps near to 1.0
+/// and far to 0.0 instead of near-to-0/far-to-1. Floating-point depth values
+/// are densest near 0.0, so this puts that precision where FPS scenes with
+/// large near/far ranges need it most (at the far plane) rather than
+/// wasting it right in front of the camera. Must be paired with a `GREATER`
+/// depth compare op and a depth buffer cleared to 0.0 instead of 1.0.
+fn perspective_matrix_fov_vulkan_reverse_z(
+    fovx_radians: f32,
+    aspect_ratio: f32,
+    near: f32,
+    far: f32) -> Mat4<f32>
+{
+    let d: f32 = 1.0 / (fovx_radians/2.0).tan();
+
+    let n = near;
+    let f = far;
+
+    Mat4::new(
+        d,    0.0,             0.0,       0.0,
+        0.0,  d*aspect_ratio,  0.0,       0.0,
+        0.0,  0.0,             n/(n-f),   -n*f/(n-f),
+        0.0,  0.0,             1.0,       0.0
+    )
+}
+
+#[cfg(test)]
+mod reverse_z_tests {
+    use siege_math::Vec4;
+    use super::perspective_matrix_fov_vulkan_reverse_z;
+
+    // NDC depth is clip.z / clip.w, for an eye-space point straight down the
+    // axis at z = depth (this engine's eye space looks down +Z).
+    fn ndc_depth_at(fovx_radians: f32, near: f32, far: f32, depth: f32) -> f32 {
+        let m = perspective_matrix_fov_vulkan_reverse_z(fovx_radians, 1.0, near, far);
+        let eye_point = Vec4::new(0.0, 0.0, depth, 1.0);
+        let clip = &m * &eye_point;
+        clip.z / clip.w
+    }
+
+    #[test]
+    fn maps_near_to_one_and_far_to_zero() {
+        let (near, far) = (0.1, 10000.0);
+        let ndc_near = ndc_depth_at(1.0472, near, far, near);
+        let ndc_far = ndc_depth_at(1.0472, near, far, far);
+        assert!((ndc_near - 1.0).abs() < 1e-4);
+        assert!((ndc_far - 0.0).abs() < 1e-4);
+    }
+}
+
+/// Generates an asymmetric perspective matrix for one eye of a stereo pair,
+/// offset from the cyclopean view by `eye_offset_x` along the local right
+/// axis. The frustum is skewed horizontally so both eyes converge on the
+/// same plane at `convergence_distance`, rather than toeing the view
+/// matrices in (which would introduce unwanted vertical parallax).
+fn perspective_matrix_fov_vulkan_asymmetric(
+    fovx_radians: f32,
+    aspect_ratio: f32,
+    near: f32,
+    far: f32,
+    eye_offset_x: f32,
+    convergence_distance: f32) -> Mat4<f32>
+{
+    let d: f32 = 1.0 / (fovx_radians/2.0).tan();
+
+    let n = near;
+    let f = far;
+
+    let skew = eye_offset_x * d / convergence_distance;
+
+    Mat4::new(
+        d,    0.0,             skew,      0.0,
+        0.0,  d*aspect_ratio,  0.0,       0.0,
+        0.0,  0.0,             -f/(n-f),  n*f/(n-f),
+        0.0,  0.0,             1.0,       0.0
+    )
+}
+
+/// A reverse-Z variant of `perspective_matrix_fov_vulkan_asymmetric`, for a
+/// stereo eye on a `Camera` with `reverse_z` set. Keeps both eyes in the same
+/// depth convention as the mono path's `perspective_matrix_fov_vulkan_reverse_z`.
+fn perspective_matrix_fov_vulkan_asymmetric_reverse_z(
+    fovx_radians: f32,
+    aspect_ratio: f32,
+    near: f32,
+    far: f32,
+    eye_offset_x: f32,
+    convergence_distance: f32) -> Mat4<f32>
+{
+    let d: f32 = 1.0 / (fovx_radians/2.0).tan();
+
+    let n = near;
+    let f = far;
+
+    let skew = eye_offset_x * d / convergence_distance;
+
+    Mat4::new(
+        d,    0.0,             skew,      0.0,
+        0.0,  d*aspect_ratio,  0.0,       0.0,
+        0.0,  0.0,             n/(n-f),   -n*f/(n-f),
+        0.0,  0.0,             1.0,       0.0
+    )
+}
+
+/// Generates an orthographic (parallel) projection matrix, mapping a centered
+/// box of eye coordinates into normalized device coordinates (a cube). Unlike
+/// the perspective matrix, clip-space w is always 1, so the bottom row is
+/// `(0, 0, 0, 1)` rather than `(0, 0, 1, 0)`.
+fn orthographic_matrix_vulkan(
+    height: f32,
+    aspect_ratio: f32,
+    near: f32,
+    far: f32) -> Mat4<f32>
+{
+    let half_height = height / 2.0;
+    let half_width = half_height * aspect_ratio;
+
+    let n = near;
+    let f = far;
+
+    Mat4::new(
+        1.0/half_width,  0.0,              0.0,        0.0,
+        0.0,             1.0/half_height,  0.0,        0.0,
+        0.0,             0.0,              1.0/(f-n),  -n/(f-n),
+        0.0,             0.0,              0.0,        1.0
+    )
+}
+
 /*
 fn look_at(eye: Point3<f32>, target: Point3<f32>, up: Direction3<f32>) -> Mat4<f32>
 {
@@ -104,46 +447,228 @@ fn look_at(eye: Point3<f32>, target: Point3<f32>, up: Direction3<f32>) -> Mat4<f
 }
  */
 
+/// Declares which sub-bindings a `CameraGfx` should expose, so a plugin
+/// author only pays for the descriptor bindings (and uniform uploads) their
+/// shaders actually read. Defaults to the split `view_proj` + `view`
+/// bindings; call `.combined(true)` to also (or instead) get the full
+/// `CameraUniforms` blob for shaders written against the old layout.
+pub struct CameraGfxBuilder {
+    view_proj: bool,
+    view: bool,
+    combined: bool,
+    stereo: bool,
+    tracks_framebuffer_resize: bool,
+}
+
+impl CameraGfxBuilder {
+    pub fn new() -> CameraGfxBuilder {
+        CameraGfxBuilder {
+            view_proj: true,
+            view: true,
+            combined: false,
+            stereo: false,
+            tracks_framebuffer_resize: true,
+        }
+    }
+
+    /// Bind the combined view-projection matrix (`CameraViewProj`) on its own.
+    pub fn view_proj(mut self, yes: bool) -> CameraGfxBuilder {
+        self.view_proj = yes;
+        self
+    }
+
+    /// Bind the view matrix, camera position, and extent (`CameraView`).
+    pub fn view(mut self, yes: bool) -> CameraGfxBuilder {
+        self.view = yes;
+        self
+    }
+
+    /// Bind the full combined `CameraUniforms` blob, for shaders that
+    /// haven't moved to the split bindings.
+    pub fn combined(mut self, yes: bool) -> CameraGfxBuilder {
+        self.combined = yes;
+        self
+    }
+
+    /// Bind left/right eye uniforms (`StereoUniforms`) for stereo (VR)
+    /// rendering. Only meaningful if `camera.stereo` is also set.
+    pub fn stereo(mut self, yes: bool) -> CameraGfxBuilder {
+        self.stereo = yes;
+        self
+    }
+
+    /// Whether `Plugin::rebuild` should resize this camera's viewport to the
+    /// whole new swapchain extent on window resize. Defaults to `true`, the
+    /// right behavior for a camera that owns the whole framebuffer. A camera
+    /// confined to a fixed sub-rectangle (split-screen, picture-in-picture)
+    /// must opt out with `.tracks_framebuffer_resize(false)` and have its
+    /// host app recompute and push a new `Viewport` via `set_viewport`
+    /// instead — a sub-rect can't be inferred from the viewport's origin or
+    /// extent alone, since e.g. the top-left pane of a split-screen layout
+    /// is indistinguishable from a full-framebuffer camera at construction
+    /// time.
+    pub fn tracks_framebuffer_resize(mut self, yes: bool) -> CameraGfxBuilder {
+        self.tracks_framebuffer_resize = yes;
+        self
+    }
+
+    pub fn build(self, renderer: &mut Renderer, camera: Arc<RwLock<Camera>>,
+                 viewport: Viewport)
+        -> Result<CameraGfx, ::siege_render::Error>
+    {
+        CameraGfx::build(renderer, camera, viewport, self)
+    }
+}
+
 pub struct CameraGfx {
     pub descriptor_set: DescriptorSet,
     pub desc_layout: DescriptorSetLayout,
-    pub uniforms_buffer: HostVisibleBuffer, // FIXME use push constants
+    pub view_proj_buffer: Option<HostVisibleBuffer>,
+    pub view_buffer: Option<HostVisibleBuffer>,
+    pub uniforms_buffer: Option<HostVisibleBuffer>, // FIXME use push constants
+    pub stereo_buffer: Option<HostVisibleBuffer>,
     pub camera_uniforms: CameraUniforms,
+    projection_cache: ProjectionCache,
+    pub stereo_uniforms: Option<StereoUniforms>,
     pub camera: Arc<RwLock<Camera>>,
-    pub camera_position_wspace: Vec4<f32>,
     pub light_dir_1: Vec4<f32>,
     pub light_dir_2: Vec4<f32>,
-    pub render_params: RenderParams
+    pub render_params: RenderParams,
+    /// The sub-rectangle of the framebuffer this camera renders into.
+    pub viewport: Viewport,
+    /// Whether `rebuild()` should resize `viewport` to match the swapchain.
+    /// True for cameras that own the whole framebuffer (the common case);
+    /// false for a fixed sub-rectangle (split-screen, picture-in-picture),
+    /// which a host app resizes explicitly via `set_viewport`.
+    tracks_framebuffer_resize: bool,
 }
 
 impl CameraGfx {
+    /// Compatibility constructor: binds the full combined `CameraUniforms`
+    /// blob at binding 0, exactly as the original single-UBO layout did, and
+    /// resizes the viewport to the whole swapchain extent on `rebuild()`.
+    /// New code should prefer `CameraGfx::builder()`.
     pub fn new(renderer: &mut Renderer,
-               camera: Arc<RwLock<Camera>>)
+               camera: Arc<RwLock<Camera>>,
+               viewport: Viewport)
+        -> Result<CameraGfx, ::siege_render::Error>
+    {
+        CameraGfxBuilder::new()
+            .view_proj(false)
+            .view(false)
+            .combined(true)
+            .build(renderer, camera, viewport)
+    }
+
+    pub fn builder() -> CameraGfxBuilder {
+        CameraGfxBuilder::new()
+    }
+
+    /// Moves this camera to a new sub-rectangle of the framebuffer (e.g. when
+    /// a host app recomputes split-screen regions on window resize).
+    pub fn set_viewport(&mut self, viewport: Viewport) {
+        self.viewport = viewport;
+        let mut camera = self.camera.write().unwrap();
+        camera.set_viewport(viewport);
+    }
+
+    fn build(renderer: &mut Renderer,
+              camera: Arc<RwLock<Camera>>,
+              viewport: Viewport,
+              opts: CameraGfxBuilder)
         -> Result<CameraGfx, ::siege_render::Error>
     {
         use dacite::core::{DescriptorType, ShaderStageFlags, BufferUsageFlags,
                            DescriptorSetLayoutCreateInfo};
 
-        let camera_uniforms = {
-            let c = camera.read().unwrap();
+        let tracks_framebuffer_resize = opts.tracks_framebuffer_resize;
+
+        let (camera_uniforms, projection_cache) = {
+            let mut c = camera.write().unwrap();
+            c.set_viewport(viewport);
             CameraUniforms::new(&c)
         };
 
-        let mut uniforms_buffer = renderer.create_host_visible_buffer::<CameraUniforms>(
-            1, BufferUsageFlags::UNIFORM_BUFFER,
-            Lifetime::Permanent, "Camera Uniforms")?;
-        uniforms_buffer.write_one::<CameraUniforms>(&camera_uniforms, None)?;
+        let mut desc_bindings = Vec::new();
+        let mut buffer_handles = Vec::new();
+
+        let mut view_proj_buffer = None;
+        if opts.view_proj {
+            let mut buf = renderer.create_host_visible_buffer::<CameraViewProj>(
+                1, BufferUsageFlags::UNIFORM_BUFFER,
+                Lifetime::Permanent, "Camera ViewProj Uniforms")?;
+            buf.write_one::<CameraViewProj>(&camera_uniforms.view_proj(), None)?;
+            buffer_handles.push(buf.inner());
+            desc_bindings.push(DescriptorSetLayoutBinding {
+                binding: desc_bindings.len() as u32,
+                descriptor_type: DescriptorType::UniformBuffer,
+                descriptor_count: 1,
+                stage_flags: ShaderStageFlags::VERTEX
+                    | ShaderStageFlags::FRAGMENT,
+                immutable_samplers: vec![],
+            });
+            view_proj_buffer = Some(buf);
+        }
+
+        let mut view_buffer = None;
+        if opts.view {
+            let mut buf = renderer.create_host_visible_buffer::<CameraView>(
+                1, BufferUsageFlags::UNIFORM_BUFFER,
+                Lifetime::Permanent, "Camera View Uniforms")?;
+            buf.write_one::<CameraView>(&camera_uniforms.view(), None)?;
+            buffer_handles.push(buf.inner());
+            desc_bindings.push(DescriptorSetLayoutBinding {
+                binding: desc_bindings.len() as u32,
+                descriptor_type: DescriptorType::UniformBuffer,
+                descriptor_count: 1,
+                stage_flags: ShaderStageFlags::VERTEX
+                    | ShaderStageFlags::FRAGMENT,
+                immutable_samplers: vec![],
+            });
+            view_buffer = Some(buf);
+        }
 
-        let desc_bindings = vec![
-            DescriptorSetLayoutBinding {
-                binding: 0, // set=0, binding=0
+        let mut uniforms_buffer = None;
+        if opts.combined {
+            let mut buf = renderer.create_host_visible_buffer::<CameraUniforms>(
+                1, BufferUsageFlags::UNIFORM_BUFFER,
+                Lifetime::Permanent, "Camera Uniforms")?;
+            buf.write_one::<CameraUniforms>(&camera_uniforms, None)?;
+            buffer_handles.push(buf.inner());
+            desc_bindings.push(DescriptorSetLayoutBinding {
+                binding: desc_bindings.len() as u32,
                 descriptor_type: DescriptorType::UniformBuffer,
-                descriptor_count: 1, // just one UBO
+                descriptor_count: 1,
                 stage_flags: ShaderStageFlags::VERTEX
                     | ShaderStageFlags::FRAGMENT,
                 immutable_samplers: vec![],
+            });
+            uniforms_buffer = Some(buf);
+        }
+
+        let stereo_uniforms = {
+            let c = camera.read().unwrap();
+            camera_uniforms.stereo(&c)
+        };
+        let mut stereo_buffer = None;
+        if opts.stereo {
+            let mut buf = renderer.create_host_visible_buffer::<StereoUniforms>(
+                1, BufferUsageFlags::UNIFORM_BUFFER,
+                Lifetime::Permanent, "Camera Stereo Uniforms")?;
+            if let Some(stereo) = stereo_uniforms {
+                buf.write_one::<StereoUniforms>(&stereo, None)?;
             }
-        ];
+            buffer_handles.push(buf.inner());
+            desc_bindings.push(DescriptorSetLayoutBinding {
+                binding: desc_bindings.len() as u32,
+                descriptor_type: DescriptorType::UniformBuffer,
+                descriptor_count: 1,
+                stage_flags: ShaderStageFlags::VERTEX
+                    | ShaderStageFlags::FRAGMENT,
+                immutable_samplers: vec![],
+            });
+            stereo_buffer = Some(buf);
+        }
 
         let (desc_layout, descriptor_set) = renderer.create_descriptor_set(
             DescriptorSetLayoutCreateInfo {
@@ -158,7 +683,7 @@ impl CameraGfx {
             use dacite::core::{OptionalDeviceSize, DescriptorBufferInfo};
 
             let mut write_sets = Vec::new();
-            for binding in desc_bindings {
+            for (binding, buffer) in desc_bindings.into_iter().zip(buffer_handles.into_iter()) {
                 write_sets.push(WriteDescriptorSet {
                     dst_set: descriptor_set.clone(),
                     dst_binding: binding.binding,
@@ -167,7 +692,7 @@ impl CameraGfx {
                     elements: WriteDescriptorSetElements::BufferInfo(
                         vec![
                             DescriptorBufferInfo {
-                                buffer: uniforms_buffer.inner(),
+                                buffer: buffer,
                                 offset: 0,
                                 range: OptionalDeviceSize::WholeSize,
                             }
@@ -182,10 +707,14 @@ impl CameraGfx {
         Ok(CameraGfx {
             descriptor_set: descriptor_set,
             desc_layout: desc_layout,
+            view_proj_buffer: view_proj_buffer,
+            view_buffer: view_buffer,
             uniforms_buffer: uniforms_buffer,
+            stereo_buffer: stereo_buffer,
             camera_uniforms: camera_uniforms,
+            projection_cache: projection_cache,
+            stereo_uniforms: stereo_uniforms,
             camera: camera,
-            camera_position_wspace: Vec4::<f32>::new(0.0, 0.0, 0.0, 1.0),
             light_dir_1: Vec4 {
                 x:  0.5773502691896258,
                 y: -0.5773502691896258,
@@ -202,13 +731,15 @@ impl CameraGfx {
                 bloom_strength: 0.6,
                 bloom_cliff: 0.35,
                 blur_level: 0.0,
-            }
+                stereo_reprojection: false,
+            },
+            viewport: viewport,
+            tracks_framebuffer_resize: tracks_framebuffer_resize,
         })
     }
 
     pub fn inv_projection(&self) -> Mat4<f32> {
-        let p: &mut CameraUniforms = self.uniforms_buffer.as_ptr().unwrap();
-        p.projection_matrix.inverse().unwrap()
+        self.camera_uniforms.projection_matrix.inverse().unwrap()
     }
 }
 
@@ -227,7 +758,11 @@ impl Plugin for CameraGfx {
         // Update the uniforms
         {
             let c = self.camera.read().unwrap();
-            self.camera_uniforms.update(&c, self.camera_position_wspace);
+            self.camera_uniforms.update(&c, &mut self.projection_cache);
+            self.stereo_uniforms = self.camera_uniforms.stereo(&c);
+        }
+        if let Some(ref mut stereo) = self.stereo_uniforms {
+            stereo.reprojected = self.render_params.stereo_reprojection as u32;
         }
 
         // Update the renderer
@@ -237,30 +772,52 @@ impl Plugin for CameraGfx {
         params.bloom_strength = self.render_params.bloom_strength;
         params.bloom_cliff = self.render_params.bloom_cliff;
         params.blur_level = self.render_params.blur_level;
+        if let Some(stereo) = self.stereo_uniforms {
+            params.stereo_left_inv_view_proj = Some(stereo.left.inv_view_proj_matrix);
+            params.stereo_right_inv_view_proj = Some(stereo.right.inv_view_proj_matrix);
+            params.stereo_reprojection = self.render_params.stereo_reprojection;
+        }
 
         Ok(false)
     }
 
     fn gpu_update(&mut self) -> ::siege_render::Result<()> {
-        self.uniforms_buffer.write_one::<CameraUniforms>(&self.camera_uniforms, None)?;
+        if let Some(ref mut buf) = self.view_proj_buffer {
+            buf.write_one::<CameraViewProj>(&self.camera_uniforms.view_proj(), None)?;
+        }
+        if let Some(ref mut buf) = self.view_buffer {
+            buf.write_one::<CameraView>(&self.camera_uniforms.view(), None)?;
+        }
+        if let Some(ref mut buf) = self.uniforms_buffer {
+            buf.write_one::<CameraUniforms>(&self.camera_uniforms, None)?;
+        }
+        if let (Some(ref mut buf), Some(stereo)) = (self.stereo_buffer.as_mut(), self.stereo_uniforms) {
+            buf.write_one::<StereoUniforms>(&stereo, None)?;
+        }
 
         Ok(())
     }
 
     fn rebuild(&mut self, extent: Extent2D) -> ::siege_render::Result<()> {
-        // We take responsibility for saving the extent into the state.camera
-        {
+        // Cameras that own the whole framebuffer track its resize; a fixed
+        // sub-rectangle (split-screen, picture-in-picture) is resized
+        // explicitly by the host app via `set_viewport` instead.
+        if self.tracks_framebuffer_resize {
+            self.viewport.extent = extent;
             let mut camera = self.camera.write().unwrap();
             camera.extent = extent;
         }
 
         // Update the uniforms
-        let p: &mut CameraUniforms = self.uniforms_buffer.as_ptr().unwrap();
         {
             let c = self.camera.read().unwrap();
-            p.update(&c, self.camera_position_wspace);
+            self.camera_uniforms.update(&c, &mut self.projection_cache);
+            self.stereo_uniforms = self.camera_uniforms.stereo(&c);
+        }
+        if let Some(ref mut stereo) = self.stereo_uniforms {
+            stereo.reprojected = self.render_params.stereo_reprojection as u32;
         }
 
-        Ok(())
+        self.gpu_update()
     }
 }
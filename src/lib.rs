@@ -5,7 +5,12 @@ extern crate siege_plugin_avatar_simple;
 extern crate siege_render;
 
 pub mod camera;
-pub use self::camera::Camera;
+pub use self::camera::{Camera, ProjectionMode, StereoConfig, Viewport};
 
 pub mod graphics;
-pub use self::graphics::{CameraUniforms, CameraGfx};
+pub use self::graphics::{CameraUniforms, CameraViewProj, CameraView,
+                          EyeUniforms, StereoUniforms,
+                          CameraGfx, CameraGfxBuilder, RenderParams};
+
+pub mod flycam;
+pub use self::flycam::{FlyCamController, FlyCamConfig, FlyCamInput};